@@ -0,0 +1,126 @@
+//! Strukturovany audit log diagnostickych a RJS udalosti (append-only NDJSON)
+//!
+//! Kazda udalost je jeden samostatny JSON radek, aby byl log greppovatelny
+//! a prehratelny; zapis se po kazde udalosti flushuje, takze i pad procesu
+//! zanecha konzistentni zaznam.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    net::SocketAddr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::client::{ClientError, RjsStatus};
+
+pub type Result<T> = std::result::Result<T, AuditError>;
+
+/// Chyby pri zapisu do audit logu
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Jeden zaznam v audit logu - dotaz nebo odpoved na RJS/DLSIP
+#[derive(Debug, Serialize)]
+struct AuditEvent {
+    timestamp: u64,
+    target: String,
+    source: Option<String>,
+    message_type: Option<u8>,
+    fields: Vec<u32>,
+    error: Option<String>,
+}
+
+/// Append-only NDJSON zapisovac audit udalosti
+pub struct AuditLog {
+    file: File,
+}
+
+impl AuditLog {
+    /// Otevre (pripadne vytvori) audit soubor pro pripojovani zaznamu
+    pub fn open(path: &str) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    fn write(&mut self, event: AuditEvent) -> Result<()> {
+        let line = serde_json::to_string(&event)?;
+        writeln!(self.file, "{line}")?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    /// Zaznamena vysledek RJS status dotazu pro dany prejezd
+    pub fn record_rjs(
+        &mut self,
+        prejezd: &str,
+        result: &std::result::Result<RjsStatus, ClientError>,
+    ) -> Result<()> {
+        let (message_type, fields, error) = match result {
+            Ok(status) => (Some(status.raw_type), status.fields.clone(), None),
+            Err(e) => (None, Vec::new(), Some(e.to_string())),
+        };
+
+        self.write(AuditEvent {
+            timestamp: unix_timestamp(),
+            target: prejezd.to_string(),
+            source: None,
+            message_type,
+            fields,
+            error,
+        })
+    }
+
+    /// Zaznamena jednu prijatou diagnostickou odpoved
+    pub fn record_diagnet_reply(&mut self, source: SocketAddr, payload: &[u8]) -> Result<()> {
+        self.write(AuditEvent {
+            timestamp: unix_timestamp(),
+            target: source.to_string(),
+            source: Some(source.to_string()),
+            message_type: None,
+            fields: vec![payload.len() as u32],
+            error: None,
+        })
+    }
+
+    /// Zaznamena, ze diagnosticky dotaz byl odeslan, ale v danem casovem okne
+    /// neprisla zadna odpoved - bez tohoto zaznamu nejde v logu odlisit
+    /// "dotazovano, 0 odpovedi" od "tento DlsIP se vubec nedotazoval"
+    pub fn record_diagnet_no_reply(&mut self, target: &str) -> Result<()> {
+        self.write(AuditEvent {
+            timestamp: unix_timestamp(),
+            target: target.to_string(),
+            source: None,
+            message_type: None,
+            fields: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Zaznamena chybu diagnostickeho dotazu pro dany cil (alias/IP)
+    pub fn record_diagnet_error(&mut self, target: &str, error: &str) -> Result<()> {
+        self.write(AuditEvent {
+            timestamp: unix_timestamp(),
+            target: target.to_string(),
+            source: None,
+            message_type: None,
+            fields: Vec::new(),
+            error: Some(error.to_string()),
+        })
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
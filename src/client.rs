@@ -0,0 +1,132 @@
+//! Klient pro dotazovani stavu RJS jednotek pres TCP
+//!
+//! Posila status-query ramec definovany v [`crate::protocol`] a ceka na
+//! odpoved, dokud nedorazi cely ramec nebo nevyprsi timeout.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+use crate::protocol::{self, MSG_STATUS_QUERY, MSG_STATUS_REPLY, MessageType};
+use crate::xml_reader::{Rjs, XmlData};
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Chyby pri komunikaci s RJS jednotkou
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Protocol(#[from] protocol::ProtocolError),
+
+    #[error("Neocekavany typ zpravy {:?} v odpovedi na status dotaz", .0)]
+    UnexpectedMessageType(MessageType),
+
+    #[error("Vyprsel casovy limit pri cekani na odpoved")]
+    Timeout,
+}
+
+/// Dekodovany stav jedne RJS jednotky
+#[derive(Debug)]
+pub struct RjsStatus {
+    pub raw_type: u8,
+    pub fields: Vec<u32>,
+}
+
+impl Rjs {
+    /// Posle status-query ramec na RJS jednotku a vrati dekodovany stav
+    pub fn query_status(&self, timeout: Duration) -> Result<RjsStatus> {
+        let deadline = Instant::now() + timeout;
+
+        let mut stream = TcpStream::connect_timeout(&(self.ip, self.port).into(), timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        stream.write_all(&protocol::encode_frame(MSG_STATUS_QUERY, &[]))?;
+
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 256];
+
+        loop {
+            if let Some((msg_type, payload, _)) = protocol::decode_frame(&buf)? {
+                if msg_type != MSG_STATUS_REPLY {
+                    return Err(ClientError::UnexpectedMessageType(msg_type));
+                }
+
+                return Ok(decode_status(&payload)?);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(ClientError::Timeout);
+            }
+            stream.set_read_timeout(Some(remaining))?;
+
+            let n = stream.read(&mut chunk)?;
+            if n == 0 {
+                return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+/// Rozparsuje payload odpovedi na status dotaz - 1B typ a dale u32 BE pole
+fn decode_status(payload: &[u8]) -> protocol::Result<RjsStatus> {
+    let mut cursor = protocol::Cursor::new(payload);
+    let raw_type = cursor.get_u8()?;
+
+    let mut fields = Vec::new();
+    while cursor.remaining() >= 4 {
+        fields.push(cursor.get_u32_be()?);
+    }
+
+    Ok(RjsStatus { raw_type, fields })
+}
+
+impl XmlData {
+    /// Dotaze vsechny nakonfigurovane RJS jednotky a vrati vysledky podle prejezdu
+    pub fn poll_all(&self, timeout: Duration) -> HashMap<String, Result<RjsStatus>> {
+        self.rjss
+            .iter()
+            .map(|rjs| (rjs.prejezd.clone(), rjs.query_status(timeout)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_status_reads_type_and_fields() {
+        let payload = [0x07, 0x00, 0x00, 0x00, 0x2A, 0x00, 0x00, 0x00, 0x01];
+
+        let status = decode_status(&payload).unwrap();
+
+        assert_eq!(status.raw_type, 0x07);
+        assert_eq!(status.fields, vec![42, 1]);
+    }
+
+    #[test]
+    fn decode_status_errs_on_empty_payload() {
+        assert!(decode_status(&[]).is_err());
+    }
+
+    #[test]
+    fn decode_status_ignores_trailing_1_to_3_bytes() {
+        // po 1B typu a jednom ctyrbytovem poli zbyvaji jen 3 B - nedotvori dalsi pole
+        let payload = [0x01, 0x00, 0x00, 0x00, 0x05, 0xAA, 0xBB, 0xCC];
+
+        let status = decode_status(&payload).unwrap();
+
+        assert_eq!(status.raw_type, 0x01);
+        assert_eq!(status.fields, vec![5]);
+    }
+}
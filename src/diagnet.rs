@@ -0,0 +1,125 @@
+//! Transport pro diagnostickou sit (DlsIP) - P2P unicast a broadcast UDP
+//!
+//! `Connection::P2P` posila dotaz primo na jednu resolvovanou adresu,
+//! `Connection::Broadcast` posila na globalni broadcast adresu (255.255.255.255) a sbira
+//! odpovedi vsech, kdo v danem case okne odpovedi.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+use crate::xml_reader::{Connection, DlsIP, XmlData};
+
+pub type Result<T> = std::result::Result<T, DiagnetError>;
+
+const DIAGNET_PORT: u16 = 5000;
+const BROADCAST_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(255, 255, 255, 255));
+
+/// Chyby pri komunikaci na diagnosticke siti
+#[derive(Debug, Error)]
+pub enum DiagnetError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("DlsIP alias se nepodarilo prelozit: {0}")]
+    Unresolved(String),
+}
+
+impl From<crate::xml_reader::Error> for DiagnetError {
+    fn from(value: crate::xml_reader::Error) -> Self {
+        DiagnetError::Unresolved(value.to_string())
+    }
+}
+
+/// Odpoved diagnostickeho hostu na dotaz
+#[derive(Debug)]
+pub struct DiagReply {
+    pub source: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
+impl DlsIP {
+    /// Cilova adresa pro tento DlsIP, vc. prekladu HOSTS aliasu
+    fn target_addr(&self) -> Result<IpAddr> {
+        Ok(self.resolve()?)
+    }
+
+    fn connection(&self) -> Connection {
+        match self {
+            DlsIP::Hosts { connection, .. } => *connection,
+            DlsIP::Static { .. } => Connection::P2P,
+        }
+    }
+
+    /// Citelny identifikator pro logovani/audit - alias, nebo statická IP
+    fn label(&self) -> String {
+        match self {
+            DlsIP::Hosts { alias, .. } => alias.clone(),
+            DlsIP::Static { ip } => ip.to_string(),
+        }
+    }
+
+    /// Posle diagnosticky dotaz a nasbira odpovedi v ramci casoveho okna
+    fn query(&self, payload: &[u8], timeout: Duration) -> Result<Vec<DiagReply>> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+
+        match self.connection() {
+            Connection::P2P => {
+                let addr = SocketAddr::new(self.target_addr()?, DIAGNET_PORT);
+                socket.send_to(payload, addr)?;
+            }
+            Connection::Broadcast => {
+                socket.set_broadcast(true)?;
+                socket.send_to(payload, SocketAddr::new(BROADCAST_ADDR, DIAGNET_PORT))?;
+            }
+        }
+
+        Ok(collect_replies(&socket, timeout)?)
+    }
+}
+
+/// Sbira UDP odpovedi, dokud nevyprsi casove okno
+fn collect_replies(socket: &UdpSocket, timeout: Duration) -> std::io::Result<Vec<DiagReply>> {
+    let deadline = Instant::now() + timeout;
+    let mut replies = Vec::new();
+    let mut buf = [0u8; 1024];
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        socket.set_read_timeout(Some(remaining))?;
+
+        match socket.recv_from(&mut buf) {
+            Ok((n, source)) => replies.push(DiagReply {
+                source,
+                payload: buf[..n].to_vec(),
+            }),
+            Err(e)
+                if e.kind() == std::io::ErrorKind::WouldBlock
+                    || e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(replies)
+}
+
+impl XmlData {
+    /// Rozesle diagnosticky dotaz vsem nakonfigurovanym DlsIP a vrati
+    /// vysledek (nebo chybu) kazdeho zvlast, aby jedna nedostupna/nerozresitelna
+    /// jednotka nezahodila odpovedi jiz ziskane od ostatnich
+    pub fn diagnet_discover(&self, timeout: Duration) -> Vec<(String, Result<Vec<DiagReply>>)> {
+        self.diagnet
+            .iter()
+            .map(|dls| (dls.label(), dls.query(&[], timeout)))
+            .collect()
+    }
+}
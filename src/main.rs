@@ -1,5 +1,13 @@
+mod audit;
+mod client;
+mod diagnet;
+mod protocol;
+mod resolve;
+mod service;
 mod xml_reader;
 
+use std::{path::PathBuf, time::Duration};
+
 use clap::Parser;
 use xml_reader::XmlData;
 
@@ -9,6 +17,9 @@ struct Cli {
     #[arg(short = 'g', long = "generate_service")]
     generate_service_file: bool,
 
+    #[arg(long = "install", help = "Nainstaluje vygenerovanou service do systemd")]
+    install: bool,
+
     #[arg(
         short = 'c',
         long = "config",
@@ -16,6 +27,13 @@ struct Cli {
         help = "Cesta k config souboru"
     )]
     config_file: Option<String>,
+
+    #[arg(
+        long = "audit",
+        value_name = "PATH",
+        help = "Cesta k audit logu (NDJSON), kam se zaznamenaji vsechny dotazy a odpovedi"
+    )]
+    audit_file: Option<String>,
 }
 
 fn main() {
@@ -25,14 +43,87 @@ fn main() {
         cli.config_file = Some("./files/IND15.xml".into());
     }
 
+    if cli.generate_service_file {
+        generate_service(&cli);
+        return;
+    }
+
     let data = XmlData::read_from_xml_file(cli.config_file.as_ref().unwrap())
         .unwrap_or_else(|e| panic!("{e}"));
 
-    for i in data.rjss {
-        println!("{:?}", i)
+    let mut audit = cli.audit_file.as_deref().map(|path| {
+        audit::AuditLog::open(path).unwrap_or_else(|e| panic!("Nelze otevrit audit log: {e}"))
+    });
+
+    for (prejezd, result) in data.poll_all(Duration::from_secs(2)) {
+        match &result {
+            Ok(status) => println!("{prejezd}: {status:?}"),
+            Err(e) => eprintln!("{prejezd}: {e}"),
+        }
+
+        if let Some(audit) = audit.as_mut() {
+            audit
+                .record_rjs(&prejezd, &result)
+                .unwrap_or_else(|e| eprintln!("Audit log selhal: {e}"));
+        }
     }
 
-    for i in data.diagnet {
-        println!("DlsIP: {:?}", i)
+    for (label, result) in data.diagnet_discover(Duration::from_secs(2)) {
+        match result {
+            Ok(replies) => {
+                if replies.is_empty() {
+                    println!("{label}: zadna odpoved");
+
+                    if let Some(audit) = audit.as_mut() {
+                        audit
+                            .record_diagnet_no_reply(&label)
+                            .unwrap_or_else(|e| eprintln!("Audit log selhal: {e}"));
+                    }
+                }
+
+                for reply in replies {
+                    println!("DlsIP reply from {}: {} B", reply.source, reply.payload.len());
+
+                    if let Some(audit) = audit.as_mut() {
+                        audit
+                            .record_diagnet_reply(reply.source, &reply.payload)
+                            .unwrap_or_else(|e| eprintln!("Audit log selhal: {e}"));
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{label}: diagnet dotaz selhal: {e}");
+
+                if let Some(audit) = audit.as_mut() {
+                    audit
+                        .record_diagnet_error(&label, &e.to_string())
+                        .unwrap_or_else(|e| eprintln!("Audit log selhal: {e}"));
+                }
+            }
+        }
+    }
+}
+
+/// Vygeneruje systemd unit pro beh ovladace s aktualne zvolenym configem a
+/// pripadne ho rovnou nainstaluje
+fn generate_service(cli: &Cli) {
+    let config_path = std::fs::canonicalize(cli.config_file.as_ref().unwrap())
+        .unwrap_or_else(|_| PathBuf::from(cli.config_file.as_ref().unwrap()));
+    let exe_path = std::env::current_exe().expect("Nelze zjistit cestu k aktualni binarce");
+
+    let unit = service::generate_unit(&exe_path, &config_path);
+
+    if cli.install {
+        const UNIT_PATH: &str = "/etc/systemd/system/rjs-driver.service";
+
+        std::fs::write(UNIT_PATH, &unit)
+            .unwrap_or_else(|e| panic!("Nelze zapsat {UNIT_PATH}: {e}"));
+
+        println!("Service soubor zapsan do {UNIT_PATH}");
+        println!("Pro aktivaci spustte:");
+        println!("  sudo systemctl daemon-reload");
+        println!("  sudo systemctl enable --now rjs-driver.service");
+    } else {
+        print!("{unit}");
     }
 }
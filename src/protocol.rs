@@ -0,0 +1,210 @@
+//! Kodek pro binarni ramce pouzivane pri komunikaci s RJS jednotkami
+//!
+//! Ramec je slozen z 2B BE delky (typ zpravy + payload), 1B typu zpravy
+//! a samotneho payloadu, coz umoznuje bufferovat castecne prijata data,
+//! dokud nedorazi cely ramec.
+
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ProtocolError>;
+
+/// Chyby pri cteni/zapisu binarnich dat
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    #[error("Neocekavany konec bufferu: potreba {needed} B, zbyva {remaining} B")]
+    UnexpectedEof { needed: usize, remaining: usize },
+}
+
+/// Typ zpravy v ramci
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageType(pub u8);
+
+pub const MSG_STATUS_QUERY: MessageType = MessageType(0x01);
+pub const MSG_STATUS_REPLY: MessageType = MessageType(0x02);
+
+/// Cursor pro cteni binarnich dat s kontrolou hranic - nikdy nepanikari,
+/// na nedostatek dat odpovida `Err`
+pub struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Pocet jeste neprectenych bytu
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn ensure(&self, n: usize) -> Result<()> {
+        if self.remaining() < n {
+            return Err(ProtocolError::UnexpectedEof {
+                needed: n,
+                remaining: self.remaining(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8> {
+        let b = self.get_bytes(1)?[0];
+        Ok(b)
+    }
+
+    pub fn get_u16_be(&mut self) -> Result<u16> {
+        let b = self.get_bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    pub fn get_u32_be(&mut self) -> Result<u32> {
+        let b = self.get_bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub fn get_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        self.ensure(n)?;
+        let slice = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    /// Precte nul-terminovany retezec
+    pub fn get_cstr(&mut self) -> Result<String> {
+        let start = self.pos;
+
+        loop {
+            if self.get_u8()? == 0 {
+                break;
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&self.buf[start..self.pos - 1]).into_owned())
+    }
+}
+
+/// Writer pro skladani binarnich zprav
+#[derive(Debug, Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn put_u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    pub fn put_u16_be(&mut self, v: u16) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    pub fn put_u32_be(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn put_cstr(&mut self, s: &str) {
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// Zabali payload do ramce: u16 BE delka (typ + payload), 1B typ, payload
+pub fn encode_frame(msg_type: MessageType, payload: &[u8]) -> Vec<u8> {
+    let mut w = Writer::new();
+    w.put_u16_be((payload.len() + 1) as u16);
+    w.put_u8(msg_type.0);
+    w.put_bytes(payload);
+    w.into_vec()
+}
+
+/// Rozparsuje jeden kompletni ramec z bufferu, pokud uz je k dispozici.
+/// Vraci `(typ, payload, pocet spotrebovanych bytu)`, nebo `None` pokud je
+/// potreba precist dalsi data.
+pub fn decode_frame(buf: &[u8]) -> Result<Option<(MessageType, Vec<u8>, usize)>> {
+    if buf.len() < 2 {
+        return Ok(None);
+    }
+
+    let len = u16::from_be_bytes([buf[0], buf[1]]) as usize;
+
+    if buf.len() < 2 + len {
+        return Ok(None);
+    }
+
+    let mut cursor = Cursor::new(&buf[2..2 + len]);
+    let msg_type = cursor.get_u8()?;
+    let payload = cursor.get_bytes(len - 1)?.to_vec();
+
+    Ok(Some((MessageType(msg_type), payload, 2 + len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_reads_within_bounds() {
+        let buf = [0x01, 0x02, 0x03, b'h', b'i', 0x00];
+        let mut cursor = Cursor::new(&buf);
+
+        assert_eq!(cursor.get_u8().unwrap(), 0x01);
+        assert_eq!(cursor.get_u16_be().unwrap(), 0x0203);
+        assert_eq!(cursor.get_cstr().unwrap(), "hi");
+    }
+
+    #[test]
+    fn cursor_errs_instead_of_panicking_on_short_buffer() {
+        let buf = [0x01];
+        let mut cursor = Cursor::new(&buf);
+
+        assert!(cursor.get_u32_be().is_err());
+    }
+
+    #[test]
+    fn decode_frame_waits_for_more_data_on_truncated_frame() {
+        // Delka rika 5 B, ale mame jen 3
+        let buf = [0x00, 0x05, 0x01, 0x02, 0x03];
+
+        assert_eq!(decode_frame(&buf).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_frame_waits_for_more_data_on_missing_length_header() {
+        let buf = [0x00];
+
+        assert_eq!(decode_frame(&buf).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_frame_rejects_zero_length_frame() {
+        // Delka 0 znamena, ze chybi i 1B typ zpravy
+        let buf = [0x00, 0x00];
+
+        assert!(decode_frame(&buf).is_err());
+    }
+
+    #[test]
+    fn encode_then_decode_roundtrips() {
+        let frame = encode_frame(MSG_STATUS_QUERY, &[0xAA, 0xBB]);
+        let (msg_type, payload, consumed) = decode_frame(&frame).unwrap().unwrap();
+
+        assert_eq!(msg_type, MSG_STATUS_QUERY);
+        assert_eq!(payload, vec![0xAA, 0xBB]);
+        assert_eq!(consumed, frame.len());
+    }
+}
@@ -0,0 +1,312 @@
+//! Preklad `DlsIP::Hosts` aliasu na IP adresu
+//!
+//! Nejprve se zkusi systemovy `/etc/hosts`, a pokud tam alias neni, provede
+//! se minimalni DNS A dotaz na nakonfigurovany resolver.
+
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr, UdpSocket},
+    sync::atomic::{AtomicU16, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::xml_reader::{ConfigError, DlsIP, Result};
+
+const HOSTS_FILE: &str = "/etc/hosts";
+const DNS_RESOLVER: &str = "127.0.0.1:53";
+const DNS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Vygeneruje transakcni id pro jeden DNS dotaz - mix casoveho zdroje
+/// entropie a atomickeho citace, aby se nedalo snadno uhodnout ani pri
+/// dvou dotazech za sebou
+fn random_query_id() -> u16 {
+    static COUNTER: AtomicU16 = AtomicU16::new(0);
+    let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u16;
+
+    nanos ^ counter
+}
+
+impl DlsIP {
+    /// Prelozi DlsIP na IP adresu - `Static` uz adresu ma, `Hosts` se
+    /// resolvuje pres hosts soubor, pripadne pres DNS
+    pub fn resolve(&self) -> Result<IpAddr> {
+        match self {
+            DlsIP::Static { ip } => Ok(*ip),
+            DlsIP::Hosts { alias, .. } => resolve_alias(alias),
+        }
+    }
+}
+
+fn resolve_alias(alias: &str) -> Result<IpAddr> {
+    if let Some(ip) = resolve_from_hosts_file(alias) {
+        return Ok(ip);
+    }
+
+    resolve_from_dns(alias).map(IpAddr::V4).map_err(|_| {
+        ConfigError::ResolutionFailed {
+            alias: alias.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Projde `/etc/hosts` a hleda alias (case-insensitive) mezi jmeny na radku
+fn resolve_from_hosts_file(alias: &str) -> Option<IpAddr> {
+    let content = fs::read_to_string(HOSTS_FILE).ok()?;
+    parse_hosts_content(&content, alias)
+}
+
+/// Rozparsuje obsah hosts souboru a hleda alias (case-insensitive) mezi
+/// jmeny na radku; kazdy radek je `<ip> <name> [aliases...]` s volitelnym
+/// `#` komentarem
+fn parse_hosts_content(content: &str, alias: &str) -> Option<IpAddr> {
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let ip = match parts.next() {
+            Some(ip) => ip,
+            None => continue,
+        };
+
+        if parts.any(|name| name.eq_ignore_ascii_case(alias)) {
+            if let Ok(ip) = ip.parse() {
+                return Some(ip);
+            }
+        }
+    }
+
+    None
+}
+
+/// Chyby pri DNS dotazu - nejsou soucasti verejneho API, volajici dostane
+/// jen obecne `ConfigError::ResolutionFailed`
+enum DnsError {
+    Io,
+    NoAnswer,
+    Malformed,
+    /// Odpoved neprisla od `DNS_RESOLVER`, nebo nesedi na nas dotaz
+    Untrusted,
+}
+
+/// Provede minimalni DNS A dotaz na `DNS_RESOLVER`
+fn resolve_from_dns(alias: &str) -> std::result::Result<Ipv4Addr, DnsError> {
+    let resolver: std::net::SocketAddr = DNS_RESOLVER.parse().map_err(|_| DnsError::Io)?;
+
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|_| DnsError::Io)?;
+    // connect() omezuje recv_from jen na pakety od resolveru, viz kontrola nize
+    socket.connect(resolver).map_err(|_| DnsError::Io)?;
+    socket
+        .set_read_timeout(Some(DNS_TIMEOUT))
+        .map_err(|_| DnsError::Io)?;
+
+    let query_id = random_query_id();
+
+    socket
+        .send(&build_query(alias, query_id))
+        .map_err(|_| DnsError::Io)?;
+
+    let mut buf = [0u8; 512];
+    let (n, from) = socket.recv_from(&mut buf).map_err(|_| DnsError::Io)?;
+
+    if from != resolver {
+        return Err(DnsError::Untrusted);
+    }
+
+    parse_response(&buf[..n], query_id)
+}
+
+/// Sestavi DNS dotaz na A zaznam daneho jmena
+fn build_query(name: &str, id: u16) -> Vec<u8> {
+    let mut packet = Vec::new();
+
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x0100u16.to_be_bytes()); // flags: recursion desired
+    packet.extend_from_slice(&1u16.to_be_bytes()); // qdcount
+    packet.extend_from_slice(&0u16.to_be_bytes()); // ancount
+    packet.extend_from_slice(&0u16.to_be_bytes()); // nscount
+    packet.extend_from_slice(&0u16.to_be_bytes()); // arcount
+
+    for label in name.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&1u16.to_be_bytes()); // qtype A
+    packet.extend_from_slice(&1u16.to_be_bytes()); // qclass IN
+
+    packet
+}
+
+/// Rozparsuje DNS odpoved a vrati prvni A zaznam z answer sekce. Overuje, ze
+/// jde skutecne o odpoved (QR=1) na nas dotaz (stejne transakcni id).
+fn parse_response(buf: &[u8], expected_id: u16) -> std::result::Result<Ipv4Addr, DnsError> {
+    if buf.len() < 12 {
+        return Err(DnsError::Malformed);
+    }
+
+    let id = u16::from_be_bytes([buf[0], buf[1]]);
+    let flags = u16::from_be_bytes([buf[2], buf[3]]);
+    const QR_BIT: u16 = 0x8000;
+
+    if id != expected_id || flags & QR_BIT == 0 {
+        return Err(DnsError::Untrusted);
+    }
+
+    let ancount = u16::from_be_bytes([buf[6], buf[7]]);
+    if ancount == 0 {
+        return Err(DnsError::NoAnswer);
+    }
+
+    let mut pos = skip_name(buf, 12)?;
+    pos += 4; // qtype + qclass dotazu
+
+    for _ in 0..ancount {
+        pos = skip_name(buf, pos)?;
+
+        if pos + 10 > buf.len() {
+            return Err(DnsError::Malformed);
+        }
+
+        let rtype = u16::from_be_bytes([buf[pos], buf[pos + 1]]);
+        let rdlength = u16::from_be_bytes([buf[pos + 8], buf[pos + 9]]) as usize;
+        pos += 10;
+
+        if pos + rdlength > buf.len() {
+            return Err(DnsError::Malformed);
+        }
+
+        if rtype == 1 && rdlength == 4 {
+            return Ok(Ipv4Addr::new(buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]));
+        }
+
+        pos += rdlength;
+    }
+
+    Err(DnsError::NoAnswer)
+}
+
+/// Preskoci DNS jmeno (vcetne kompresnich ukazatelu 0xC0) a vrati pozici za nim
+fn skip_name(buf: &[u8], mut pos: usize) -> std::result::Result<usize, DnsError> {
+    loop {
+        if pos >= buf.len() {
+            return Err(DnsError::Malformed);
+        }
+
+        let len = buf[pos];
+
+        if len == 0 {
+            return Ok(pos + 1);
+        } else if len & 0xC0 == 0xC0 {
+            return Ok(pos + 2);
+        } else {
+            pos += 1 + len as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hosts_content_finds_alias_case_insensitive() {
+        let hosts = "127.0.0.1 localhost\n10.0.0.5 DlsIP-Server  alt-name # komentar\n";
+
+        assert_eq!(
+            parse_hosts_content(hosts, "dlsip-server"),
+            Some("10.0.0.5".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_hosts_content_skips_blank_and_comment_only_lines() {
+        let hosts = "\n   \n# jen komentar\n10.0.0.5 target\n";
+
+        assert_eq!(
+            parse_hosts_content(hosts, "target"),
+            Some("10.0.0.5".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_hosts_content_returns_none_for_unknown_alias() {
+        let hosts = "10.0.0.5 target\n";
+
+        assert_eq!(parse_hosts_content(hosts, "missing"), None);
+    }
+
+    /// Sestavi minimalni DNS odpoved s jednim A zaznamem, jehoz jmeno
+    /// odkazuje na dotaz pres kompresni ukazatel na offset 12
+    fn sample_dns_response(id: u16, qr_bit_set: bool, ip: [u8; 4]) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        buf.extend_from_slice(&id.to_be_bytes());
+        buf.extend_from_slice(&(if qr_bit_set { 0x8180u16 } else { 0x0100u16 }).to_be_bytes());
+        buf.extend_from_slice(&1u16.to_be_bytes()); // qdcount
+        buf.extend_from_slice(&1u16.to_be_bytes()); // ancount
+        buf.extend_from_slice(&0u16.to_be_bytes()); // nscount
+        buf.extend_from_slice(&0u16.to_be_bytes()); // arcount
+
+        // otazka: jmeno "a", QTYPE=A, QCLASS=IN
+        buf.push(1);
+        buf.push(b'a');
+        buf.push(0);
+        buf.extend_from_slice(&1u16.to_be_bytes());
+        buf.extend_from_slice(&1u16.to_be_bytes());
+
+        // odpoved: jmeno pres kompresni ukazatel na offset 12 (zacatek otazky)
+        buf.extend_from_slice(&[0xC0, 0x0C]);
+        buf.extend_from_slice(&1u16.to_be_bytes()); // type A
+        buf.extend_from_slice(&1u16.to_be_bytes()); // class IN
+        buf.extend_from_slice(&0u32.to_be_bytes()); // ttl
+        buf.extend_from_slice(&4u16.to_be_bytes()); // rdlength
+        buf.extend_from_slice(&ip);
+
+        buf
+    }
+
+    #[test]
+    fn parse_response_follows_compression_pointer_and_returns_a_record() {
+        let response = sample_dns_response(0x1234, true, [10, 0, 0, 1]);
+
+        let ip = parse_response(&response, 0x1234)
+            .ok()
+            .expect("expected an A record");
+        assert_eq!(ip, Ipv4Addr::new(10, 0, 0, 1));
+    }
+
+    #[test]
+    fn parse_response_rejects_mismatched_transaction_id() {
+        let response = sample_dns_response(0xFFFF, true, [10, 0, 0, 1]);
+
+        assert!(parse_response(&response, 0x1234).is_err());
+    }
+
+    #[test]
+    fn parse_response_rejects_packet_without_qr_bit() {
+        let response = sample_dns_response(0x1234, false, [10, 0, 0, 1]);
+
+        assert!(parse_response(&response, 0x1234).is_err());
+    }
+
+    #[test]
+    fn parse_response_rejects_truncated_header() {
+        assert!(parse_response(&[0x12, 0x34], 0x1234).is_err());
+    }
+
+    #[test]
+    fn random_query_id_differs_across_consecutive_calls() {
+        let ids: Vec<u16> = (0..8).map(|_| random_query_id()).collect();
+        assert!(ids.windows(2).any(|w| w[0] != w[1]));
+    }
+}
@@ -0,0 +1,50 @@
+//! Generovani systemd unit souboru pro beh ovladace jako demona
+
+use std::path::Path;
+
+/// Sestavi obsah systemd unit souboru pro dany config a cestu k binarce
+pub fn generate_unit(exe_path: &Path, config_path: &Path) -> String {
+    format!(
+        "[Unit]\n\
+         Description=rjs-driver\n\
+         After=network-online.target\n\
+         Wants=network-online.target\n\
+         \n\
+         [Service]\n\
+         ExecStart={} --config {}\n\
+         Restart=on-failure\n\
+         \n\
+         [Install]\n\
+         WantedBy=multi-user.target\n",
+        exe_path.display(),
+        config_path.display(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_unit_contains_exe_and_config_paths() {
+        let unit = generate_unit(
+            Path::new("/usr/local/bin/rjs-driver"),
+            Path::new("/etc/rjs-driver/IND15.xml"),
+        );
+
+        assert!(unit.contains(
+            "ExecStart=/usr/local/bin/rjs-driver --config /etc/rjs-driver/IND15.xml"
+        ));
+    }
+
+    #[test]
+    fn generate_unit_has_expected_sections_and_keys() {
+        let unit = generate_unit(Path::new("/bin/rjs-driver"), Path::new("/etc/config.xml"));
+
+        assert!(unit.starts_with("[Unit]\n"));
+        assert!(unit.contains("\n[Service]\n"));
+        assert!(unit.contains("\n[Install]\n"));
+        assert!(unit.contains("Restart=on-failure\n"));
+        assert!(unit.contains("WantedBy=multi-user.target\n"));
+    }
+}
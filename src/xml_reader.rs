@@ -6,7 +6,7 @@ use quick_xml::{
 };
 use thiserror::Error;
 
-type Result<T> = std::result::Result<T, Error>;
+pub(crate) type Result<T> = std::result::Result<T, Error>;
 
 /// Macro pro parsovani s pekne formatovanou pripadnou chybou
 macro_rules! parse {
@@ -29,26 +29,21 @@ pub struct XmlData {
 /// Data RJS
 #[derive(Debug)]
 pub struct Rjs {
-    prejezd: String,
-    ip: IpAddr,
-    port: u16,
+    pub(crate) prejezd: String,
+    pub(crate) ip: IpAddr,
+    pub(crate) port: u16,
     rjs_type: String,
 }
 
 /// Data DlsIP
 #[derive(Debug)]
 pub enum DlsIP {
-    Hosts {
-        alias: String,
-        connection: Connection,
-    },
-    Static {
-        ip: IpAddr,
-    },
+    Hosts { alias: String, connection: Connection },
+    Static { ip: IpAddr },
 }
 
 /// DlsIP connection type
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Connection {
     P2P,
     Broadcast,
@@ -131,8 +126,8 @@ fn find_attribute<'a>(attrs: &Attributes<'a>, data: &str) -> Result<String> {
     }
 }
 
-/// Prida location data k pripadne chybe
-fn add_location_data<T>(res: &mut Result<T>, path: &[Option<BytesStart>]) {
+/// Prida location data (cesta elementu + radek/sloupec) k pripadne chybe
+fn add_location_data<T>(res: &mut Result<T>, path: &[Option<BytesStart>], xml: &str, offset: u64) {
     if let Err(Error::ConfigError { location, .. }) = res {
         let path_str = path
             .iter()
@@ -141,8 +136,29 @@ fn add_location_data<T>(res: &mut Result<T>, path: &[Option<BytesStart>]) {
             .collect::<Vec<_>>()
             .join("/");
 
-        *location = path_str;
+        let (line, col) = line_col(xml, offset);
+
+        *location = format!("{path_str} (line {line}, col {col})");
+    }
+}
+
+/// Prevede bytovy offset v bufferu (pozice z quick_xml je `u64`) na 1-based radek a sloupec
+fn line_col(xml: &str, offset: u64) -> (usize, usize) {
+    let offset = (offset as usize).min(xml.len());
+
+    let mut line = 1;
+    let mut col = 1;
+
+    for ch in xml[..offset].chars() {
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
     }
+
+    (line, col)
 }
 
 impl XmlData {
@@ -163,14 +179,17 @@ impl XmlData {
         let mut data = Self::default();
 
         // xml reader
-        let mut xml = quick_xml::Reader::from_str(&xml);
+        let mut reader = quick_xml::Reader::from_str(&xml);
 
         // data o aktualni pozici v xml souboru s maximalni hloubkou 10
         let mut depth = 0;
         let mut path: [Option<BytesStart>; _] = [const { None }; 10];
 
         loop {
-            match xml.read_event() {
+            // pozice v bufferu pred nactenim udalosti = zacatek elementu, pro hlaseni line:col u chyb
+            let offset = reader.buffer_position();
+
+            match reader.read_event() {
                 Ok(e) => {
                     match e {
                         // Kontroluje aktualni pozici
@@ -197,14 +216,21 @@ impl XmlData {
                                 QName(b"rjs") => {
                                     let mut rjs = Rjs::from_attributes(e.attributes());
 
-                                    add_location_data(&mut rjs, &path);
+                                    add_location_data(&mut rjs, &path, &xml, offset);
 
                                     data.rjss.push(rjs?);
                                 }
                                 QName(b"dlsip") => {
                                     let mut dlsip = DlsIP::from_attributes(e.attributes());
 
-                                    add_location_data(&mut dlsip, &path);
+                                    // Zkusi hned overit, ze se HOSTS alias da prelozit
+                                    if let Ok(parsed) = &dlsip {
+                                        if let Err(resolve_err) = parsed.resolve() {
+                                            dlsip = Err(resolve_err);
+                                        }
+                                    }
+
+                                    add_location_data(&mut dlsip, &path, &xml, offset);
 
                                     data.diagnet.push(dlsip?);
                                 }
@@ -261,6 +287,9 @@ pub enum ConfigError {
 
     #[error("Invalid value {} in {} arribute ", .value, .name)]
     InvalidValue { name: String, value: String },
+
+    #[error("Nepodarilo se prelozit alias {}", .alias)]
+    ResolutionFailed { alias: String },
 }
 
 /// Prevod ConfigError na Error bez lokace
@@ -282,3 +311,36 @@ impl Default for XmlData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_on_first_line() {
+        assert_eq!(line_col("<rjss><rjs/></rjss>", 7), (1, 8));
+    }
+
+    #[test]
+    fn line_col_counts_newlines() {
+        let xml = "<rjss>\n  <rjs/>\n</rjss>";
+        // offset 9 je zacatek "<rjs" na druhem radku, po dvou mezerach
+        assert_eq!(line_col(xml, 9), (2, 3));
+    }
+
+    #[test]
+    fn line_col_clamps_offset_past_end_of_buffer() {
+        let xml = "<rjss/>";
+        assert_eq!(line_col(xml, 1000), (1, xml.len() + 1));
+    }
+
+    #[test]
+    fn read_from_xml_reports_line_and_col_for_missing_attribute() {
+        let xml = "<rjss>\n  <rjs prejezd=\"P1\" ip=\"1.2.3.4\" type=\"t\"/>\n</rjss>";
+
+        let err = XmlData::read_from_xml(xml.to_string()).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("line 2"), "unexpected message: {message}");
+    }
+}